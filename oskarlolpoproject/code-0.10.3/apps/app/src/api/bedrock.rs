@@ -1,9 +1,28 @@
+use async_trait::async_trait;
 use crate::api::Result;
+use futures_util::StreamExt;
+use minisign_verify::{PublicKey, Signature};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use sha2::{Digest, Sha256, Sha512};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::process::Stdio;
 use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Public key used to verify detached `minisign` signatures on Bedrock appx
+/// downloads, mirroring the Millennium updater's signature-pinning approach
+const BEDROCK_MINISIGN_PUBLIC_KEY: &str =
+    "RWQwEgiPP8Y3mR3m4ymSaJSxqsaE5RGGb2Cb7HGFRv5DvFhx7PxwqMwW";
+
+/// Public client ID used for the Microsoft device-code OAuth flow, the same
+/// "consumers" client other third-party Minecraft launchers register under
+const MSA_CLIENT_ID: &str = "00000000402b5328";
+
+/// Community-maintained index of Bedrock appx release URLs, fetched
+/// directly instead of shelling out to a Node script to regenerate it
+const BEDROCK_VERSION_MANIFEST_URL: &str =
+    "https://raw.githubusercontent.com/Bedrock-OSS/bedrock-version-index/main/versions.json";
 
 pub fn init<R: tauri::Runtime>() -> tauri::plugin::TauriPlugin<R> {
     tauri::plugin::Builder::new("bedrock")
@@ -15,6 +34,14 @@ pub fn init<R: tauri::Runtime>() -> tauri::plugin::TauriPlugin<R> {
             bedrock_run_instance,
             bedrock_list_instances,
             bedrock_remove_instance,
+            bedrock_add_addon,
+            bedrock_list_addons,
+            bedrock_remove_addon,
+            bedrock_auth_login,
+            bedrock_auth_refresh,
+            bedrock_set_instance_account,
+            bedrock_export_instance,
+            bedrock_import_instance,
         ])
         .build()
 }
@@ -24,9 +51,10 @@ pub struct BedrockVersion {
     pub id: String,
     pub url: String,
     pub sha256: Option<String>,
+    pub signature: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct BedrockVersionsResponse {
     pub versions: Vec<BedrockVersion>,
 }
@@ -38,96 +66,523 @@ pub struct BedrockInstance {
     pub path: String,
     pub installed: bool,
     pub appx_path: Option<String>,
+    pub verified: bool,
+    #[serde(default)]
+    pub addons: Vec<BedrockAddon>,
+    #[serde(default)]
+    pub account: Option<uuid::Uuid>,
+}
+
+/// Which pack folder an addon belongs in once unpacked into an instance
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AddonKind {
+    Behavior,
+    Resource,
+}
+
+impl AddonKind {
+    fn folder_name(&self) -> &'static str {
+        match self {
+            AddonKind::Behavior => "behavior_packs",
+            AddonKind::Resource => "resource_packs",
+        }
+    }
+}
+
+/// The backend an addon should be resolved from, mirroring how mcman
+/// resolves server content from multiple registries
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AddonSourceConfig {
+    Modrinth { project_id: String, version_id: String },
+    CurseForge { mod_id: String, file_id: String },
+    GithubRelease { owner: String, repo: String, tag: String, asset: String },
+    Url { url: String },
+}
+
+/// A behavior or resource pack installed into a [`BedrockInstance`]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BedrockAddon {
+    pub id: String,
+    pub name: String,
+    pub kind: AddonKind,
+    pub source: AddonSourceConfig,
+    pub version: String,
+    pub file_path: String,
+}
+
+/// Resolves an addon into a downloadable file written to `dest`, implemented
+/// once per backend (Modrinth, CurseForge, GitHub releases, direct URL). The
+/// built-in backends resolve from their own configured identifiers and
+/// ignore `id`.
+#[async_trait]
+pub trait AddonSource {
+    async fn resolve(&self, id: &str, dest: PathBuf) -> Result<FileToDownload>;
+}
+
+struct ModrinthAddonSource {
+    version_id: String,
+}
+
+#[async_trait]
+impl AddonSource for ModrinthAddonSource {
+    async fn resolve(&self, _id: &str, dest: PathBuf) -> Result<FileToDownload> {
+        let client = reqwest::Client::new();
+        let version: serde_json::Value = client
+            .get(format!("https://api.modrinth.com/v2/version/{}", self.version_id))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to query Modrinth version: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse Modrinth version: {}", e))?;
+
+        let files = version["files"].as_array().ok_or("Modrinth version has no files")?;
+        let file = files
+            .iter()
+            .find(|f| f["primary"].as_bool().unwrap_or(false))
+            .or_else(|| files.first())
+            .ok_or("Modrinth version has no files")?;
+        let url = file["url"].as_str().ok_or("Modrinth file missing url")?.to_string();
+        let hash = file["hashes"]["sha512"]
+            .as_str()
+            .map(|s| FileHash::Sha512(s.to_string()));
+        Ok(FileToDownload { url, hash, dest })
+    }
+}
+
+struct CurseForgeAddonSource {
+    mod_id: String,
+    file_id: String,
+}
+
+#[async_trait]
+impl AddonSource for CurseForgeAddonSource {
+    async fn resolve(&self, _id: &str, dest: PathBuf) -> Result<FileToDownload> {
+        // CurseForge's Core API requires a per-application key; there is no
+        // public, unauthenticated download endpoint.
+        let api_key = std::env::var("CURSEFORGE_API_KEY")
+            .map_err(|_| "CURSEFORGE_API_KEY environment variable is not set")?;
+
+        let client = reqwest::Client::new();
+        let response: serde_json::Value = client
+            .get(format!(
+                "https://api.curseforge.com/v1/mods/{}/files/{}/download-url",
+                self.mod_id, self.file_id
+            ))
+            .header("x-api-key", api_key)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to resolve CurseForge download: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse CurseForge response: {}", e))?;
+
+        let url = response["data"]
+            .as_str()
+            .ok_or("CurseForge response missing download url")?
+            .to_string();
+
+        Ok(FileToDownload { url, hash: None, dest })
+    }
+}
+
+struct GithubReleaseAddonSource {
+    owner: String,
+    repo: String,
+    tag: String,
+    asset: String,
+}
+
+#[async_trait]
+impl AddonSource for GithubReleaseAddonSource {
+    async fn resolve(&self, _id: &str, dest: PathBuf) -> Result<FileToDownload> {
+        let url = format!(
+            "https://github.com/{}/{}/releases/download/{}/{}",
+            self.owner, self.repo, self.tag, self.asset
+        );
+        Ok(FileToDownload { url, hash: None, dest })
+    }
+}
+
+struct UrlAddonSource {
+    url: String,
+}
+
+#[async_trait]
+impl AddonSource for UrlAddonSource {
+    async fn resolve(&self, _id: &str, dest: PathBuf) -> Result<FileToDownload> {
+        Ok(FileToDownload { url: self.url.clone(), hash: None, dest })
+    }
+}
+
+fn addon_source_for(source: &AddonSourceConfig) -> Box<dyn AddonSource + Send + Sync> {
+    match source {
+        AddonSourceConfig::Modrinth { version_id, .. } => Box::new(ModrinthAddonSource {
+            version_id: version_id.clone(),
+        }),
+        AddonSourceConfig::CurseForge { mod_id, file_id } => Box::new(CurseForgeAddonSource {
+            mod_id: mod_id.clone(),
+            file_id: file_id.clone(),
+        }),
+        AddonSourceConfig::GithubRelease { owner, repo, tag, asset } => {
+            Box::new(GithubReleaseAddonSource {
+                owner: owner.clone(),
+                repo: repo.clone(),
+                tag: tag.clone(),
+                asset: asset.clone(),
+            })
+        }
+        AddonSourceConfig::Url { url } => Box::new(UrlAddonSource { url: url.clone() }),
+    }
+}
+
+/// A checksum a downloaded [`FileToDownload`] is expected to match, tagged
+/// with which digest produced it since sources report different algorithms
+/// (Bedrock appx releases publish SHA-256, Modrinth reports SHA-512)
+#[derive(Debug, Clone)]
+pub enum FileHash {
+    Sha256(String),
+    Sha512(String),
+}
+
+/// Describes a single file to be fetched by the [`Downloader`]
+#[derive(Debug, Clone)]
+pub struct FileToDownload {
+    pub url: String,
+    pub hash: Option<FileHash>,
+    pub dest: PathBuf,
+}
+
+/// Structured progress payload forwarded to the frontend via `window.emit`
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "camelCase")]
+pub enum BedrockDownloadEvent {
+    Started { total_bytes: Option<u64> },
+    Progress { downloaded: u64, total: Option<u64> },
+    Finished,
+}
+
+/// Streams a [`FileToDownload`] to disk, resuming a partial download via
+/// an HTTP `Range` header and reporting chunked progress through a callback
+pub struct Downloader {
+    client: reqwest::Client,
+}
+
+impl Downloader {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub async fn download<F>(&self, file: &FileToDownload, mut on_progress: F) -> Result<()>
+    where
+        F: FnMut(BedrockDownloadEvent),
+    {
+        if let Some(parent) = file.dest.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .map_err(|e| format!("Failed to create download directory: {}", e))?;
+        }
+
+        let already_downloaded = match fs::metadata(&file.dest).await {
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        };
+
+        let mut request = self.client.get(&file.url);
+        if already_downloaded > 0 {
+            request = request.header("Range", format!("bytes={}-", already_downloaded));
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to start download: {}", e))?;
+
+        let status = response.status();
+
+        // A 416 on a ranged request means the server has nothing past our
+        // `Range` offset to send, i.e. the file on disk is already complete.
+        if status.as_u16() == 416 && already_downloaded > 0 {
+            on_progress(BedrockDownloadEvent::Finished);
+            return Ok(());
+        }
+
+        if !status.is_success() && status.as_u16() != 206 {
+            return Err(format!("Download failed with status: {}", status).into());
+        }
+
+        let resuming = already_downloaded > 0 && status.as_u16() == 206;
+        let content_length = response.content_length();
+        let total_bytes = match (resuming, content_length) {
+            (true, Some(remaining)) => Some(already_downloaded + remaining),
+            (false, Some(total)) => Some(total),
+            _ => None,
+        };
+
+        on_progress(BedrockDownloadEvent::Started { total_bytes });
+
+        let mut out_file = fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resuming)
+            .truncate(!resuming)
+            .open(&file.dest)
+            .await
+            .map_err(|e| format!("Failed to open destination file: {}", e))?;
+
+        let mut downloaded = if resuming { already_downloaded } else { 0 };
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| format!("Failed to read download chunk: {}", e))?;
+            out_file
+                .write_all(&chunk)
+                .await
+                .map_err(|e| format!("Failed to write download chunk: {}", e))?;
+            downloaded += chunk.len() as u64;
+            on_progress(BedrockDownloadEvent::Progress {
+                downloaded,
+                total: total_bytes,
+            });
+        }
+
+        out_file
+            .flush()
+            .await
+            .map_err(|e| format!("Failed to flush downloaded file: {}", e))?;
+
+        on_progress(BedrockDownloadEvent::Finished);
+
+        Ok(())
+    }
+}
+
+/// Verify `path` against whichever digest `hash` was produced with, deleting
+/// the file on mismatch
+async fn verify_file_hash(path: &Path, hash: &FileHash) -> Result<()> {
+    match hash {
+        FileHash::Sha256(expected) => verify_digest::<Sha256>(path, expected, "SHA-256").await,
+        FileHash::Sha512(expected) => verify_digest::<Sha512>(path, expected, "SHA-512").await,
+    }
+}
+
+/// Stream `path` through digest `D` and compare against `expected` (a
+/// lowercase hex string), deleting the file on mismatch
+async fn verify_digest<D: Digest>(path: &Path, expected: &str, label: &str) -> Result<()> {
+    let mut file = fs::File::open(path)
+        .await
+        .map_err(|e| format!("Failed to open file for hashing: {}", e))?;
+
+    let mut hasher = D::new();
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let read = file
+            .read(&mut buf)
+            .await
+            .map_err(|e| format!("Failed to read file for hashing: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buf[..read]);
+    }
+
+    let digest = format!("{:x}", hasher.finalize());
+    if !digest.eq_ignore_ascii_case(expected) {
+        let _ = fs::remove_file(path).await;
+        return Err(format!(
+            "{} mismatch for {}: expected {}, got {}",
+            label,
+            path.display(),
+            expected,
+            digest
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Verify a detached `minisign` signature over the bytes at `path` using the
+/// pinned [`BEDROCK_MINISIGN_PUBLIC_KEY`]
+async fn verify_minisign(path: &Path, signature: &str) -> Result<()> {
+    let public_key = PublicKey::from_base64(BEDROCK_MINISIGN_PUBLIC_KEY)
+        .map_err(|e| format!("Invalid Bedrock minisign public key: {}", e))?;
+    let signature = Signature::decode_string(signature)
+        .map_err(|e| format!("Invalid Bedrock appx signature: {}", e))?;
+
+    let bytes = fs::read(path)
+        .await
+        .map_err(|e| format!("Failed to read appx for signature verification: {}", e))?;
+
+    public_key
+        .verify(&bytes, &signature, false)
+        .map_err(|e| format!("Bedrock appx signature verification failed: {}", e).into())
 }
 
-/// Get available Bedrock versions from the generated version.json
-/// Uses the generate_version_json.js script to fetch latest versions
+/// Get available Bedrock versions, fetching the manifest directly instead of
+/// shelling out to Node, and falling back to the on-disk cache when the
+/// CDN/version endpoints are flaky
 #[tauri::command]
 pub async fn bedrock_get_versions() -> Result<BedrockVersionsResponse> {
-    // First, try to update versions using the Node.js script
-    let script_path = get_script_path("generate_version_json.js").await?;
-    
-    // Run the Node.js script to update version.json
-    let output = Command::new("node")
-        .arg(&script_path)
-        .current_dir(get_scripts_dir().await?)
-        .output();
+    let cached = read_version_cache().await;
+    if let Some(cached) = &cached {
+        if unix_timestamp().saturating_sub(cached.fetched_at) < VERSION_CACHE_TTL_SECS {
+            return Ok(cached.response.clone());
+        }
+    }
 
-    match output {
-        Ok(output) => {
-            if !output.status.success() {
-                eprintln!("Warning: Failed to update Bedrock versions: {}", 
-                    String::from_utf8_lossy(&output.stderr));
-            }
+    let client = reqwest::Client::new();
+    match retry_with_backoff(|| fetch_version_manifest(&client)).await {
+        Ok(response) => {
+            write_version_cache(&response).await?;
+            Ok(response)
         }
         Err(e) => {
-            eprintln!("Warning: Could not run version update script: {}", e);
+            if let Some(cached) = cached {
+                eprintln!(
+                    "Warning: failed to refresh Bedrock versions, serving stale cache: {}",
+                    e
+                );
+                Ok(cached.response)
+            } else {
+                Err(e)
+            }
         }
     }
+}
 
-    // Read the version.json file
-    let version_file_path = get_scripts_dir().await?.join("../version.json");
-    match fs::read_to_string(&version_file_path).await {
-        Ok(content) => {
-            let versions: BedrockVersionsResponse = serde_json::from_str(&content)
-                .map_err(|e| format!("Failed to parse version.json: {}", e))?;
-            Ok(versions)
-        }
-        Err(_) => {
-            // Return empty list if file doesn't exist
-            Ok(BedrockVersionsResponse {
-                versions: Vec::new(),
+/// Fetch and parse the Bedrock appx version manifest
+async fn fetch_version_manifest(client: &reqwest::Client) -> Result<BedrockVersionsResponse> {
+    let manifest: serde_json::Value = client
+        .get(BEDROCK_VERSION_MANIFEST_URL)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch Bedrock version manifest: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Bedrock version manifest: {}", e))?;
+
+    let entries = manifest["versions"]
+        .as_array()
+        .ok_or("Bedrock version manifest missing versions array")?;
+
+    let versions = entries
+        .iter()
+        .filter_map(|entry| {
+            Some(BedrockVersion {
+                id: entry["version"].as_str()?.to_string(),
+                url: entry["uwp_url"].as_str()?.to_string(),
+                sha256: entry["sha256"].as_str().map(|s| s.to_string()),
+                signature: entry["signature"].as_str().map(|s| s.to_string()),
             })
+        })
+        .collect();
+
+    Ok(BedrockVersionsResponse { versions })
+}
+
+/// Run an async operation up to 3 times with doubling delay between
+/// attempts, since the Bedrock CDN/version endpoints are known to be flaky
+async fn retry_with_backoff<T, F, Fut>(mut attempt: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    const MAX_ATTEMPTS: u32 = 3;
+    let mut delay = std::time::Duration::from_millis(500);
+    let mut last_err = None;
+
+    for attempt_number in 1..=MAX_ATTEMPTS {
+        match attempt().await {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                last_err = Some(e);
+                if attempt_number < MAX_ATTEMPTS {
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                }
+            }
         }
     }
+
+    Err(last_err.unwrap_or_else(|| "Request failed after retries".into()))
+}
+
+/// How long a cached version manifest is served before being refreshed
+const VERSION_CACHE_TTL_SECS: u64 = 60 * 60;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedVersions {
+    fetched_at: u64,
+    response: BedrockVersionsResponse,
+}
+
+async fn version_cache_path() -> Result<PathBuf> {
+    let app_dir = dirs::data_dir()
+        .ok_or("Failed to get app data directory")?
+        .join("com.modrinth.app");
+    fs::create_dir_all(&app_dir).await
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    Ok(app_dir.join("bedrock_versions_cache.json"))
 }
 
-/// Download a specific Bedrock version using download_bedrock_appx.js
+async fn read_version_cache() -> Option<CachedVersions> {
+    let path = version_cache_path().await.ok()?;
+    let content = fs::read_to_string(&path).await.ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+async fn write_version_cache(response: &BedrockVersionsResponse) -> Result<()> {
+    let path = version_cache_path().await?;
+    let cached = CachedVersions {
+        fetched_at: unix_timestamp(),
+        response: response.clone(),
+    };
+    let json = serde_json::to_string_pretty(&cached)
+        .map_err(|e| format!("Failed to serialize version cache: {}", e))?;
+    fs::write(&path, json).await
+        .map_err(|e| format!("Failed to write version cache: {}", e))?;
+    Ok(())
+}
+
+/// Download a specific Bedrock version, streaming the .appx from its CDN URL
+/// with byte-level progress and HTTP range-resume for interrupted downloads
 #[tauri::command]
 pub async fn bedrock_download_version(
     version: String,
     window: tauri::Window,
 ) -> Result<String> {
-    let script_path = get_script_path("download_bedrock_appx.js").await?;
+    let versions = bedrock_get_versions().await?;
+    let bedrock_version = versions
+        .versions
+        .into_iter()
+        .find(|v| v.id == version)
+        .ok_or_else(|| format!("Unknown Bedrock version: {}", version))?;
+
     let scripts_dir = get_scripts_dir().await?;
-    
-    // Create downloads directory if it doesn't exist
     let downloads_dir = scripts_dir.join("../downloads");
-    fs::create_dir_all(&downloads_dir).await
-        .map_err(|e| format!("Failed to create downloads directory: {}", e))?;
+    let appx_file = downloads_dir.join(format!("{}.Appx", version));
 
-    // Run the download script with the specified version
-    let mut child = Command::new("node")
-        .arg(&script_path)
-        .current_dir(&scripts_dir)
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| format!("Failed to start download script: {}", e))?;
+    let file = FileToDownload {
+        url: bedrock_version.url,
+        hash: bedrock_version.sha256.map(FileHash::Sha256),
+        dest: appx_file.clone(),
+    };
 
-    // Send version to stdin
-    if let Some(stdin) = child.stdin.as_mut() {
-        use std::io::Write;
-        stdin.write_all(format!("{}\n", version).as_bytes())
-            .map_err(|e| format!("Failed to send version to script: {}", e))?;
-    }
+    let downloader = Downloader::new();
+    downloader
+        .download(&file, |event| {
+            let _ = window.emit("bedrock-download-progress", &event);
+        })
+        .await?;
 
-    // Wait for completion and get output
-    let output = child.wait_with_output()
-        .map_err(|e| format!("Failed to wait for download script: {}", e))?;
-    
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Download script failed: {}", stderr).into());
+    if let Some(hash) = &file.hash {
+        verify_file_hash(&appx_file, hash).await?;
     }
-    
-    // Emit completion event
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let _ = window.emit("bedrock-download-progress", "Download completed successfully!");
 
-    // Return path to downloaded file
-    let appx_file = downloads_dir.join(format!("{}.Appx", version));
     Ok(appx_file.to_string_lossy().to_string())
 }
 
@@ -135,8 +590,13 @@ pub async fn bedrock_download_version(
 #[tauri::command]
 pub async fn bedrock_install_version(
     appx_path: String,
+    signature: Option<String>,
     window: tauri::Window,
 ) -> Result<()> {
+    if let Some(signature) = &signature {
+        verify_minisign(Path::new(&appx_path), signature).await?;
+    }
+
     let script_path = get_script_path("install_bedrock_appx.js").await?;
     let scripts_dir = get_scripts_dir().await?;
 
@@ -182,11 +642,18 @@ pub async fn bedrock_create_instance(
     // Download the version
     let _ = window.emit("bedrock-creation-status", "Downloading Bedrock version...");
     let appx_path = bedrock_download_version(version.clone(), window.clone()).await?;
-    
+
+    let versions = bedrock_get_versions().await?;
+    let signature = versions
+        .versions
+        .into_iter()
+        .find(|v| v.id == version)
+        .and_then(|v| v.signature);
+
     // Install the version
     let _ = window.emit("bedrock-creation-status", "Installing Bedrock version...");
-    bedrock_install_version(appx_path.clone(), window.clone()).await?;
-    
+    bedrock_install_version(appx_path.clone(), signature.clone(), window.clone()).await?;
+
     // Create instance metadata
     let instance = BedrockInstance {
         name: name.clone(),
@@ -194,6 +661,9 @@ pub async fn bedrock_create_instance(
         path: format!("bedrock_{}", name.replace(" ", "_").to_lowercase()),
         installed: true,
         appx_path: Some(appx_path),
+        verified: signature.is_some(),
+        addons: Vec::new(),
+        account: None,
     };
 
     // Save instance to configuration
@@ -205,17 +675,43 @@ pub async fn bedrock_create_instance(
 
 /// Run a Bedrock instance by launching the installed .appx application
 #[tauri::command]
-pub async fn bedrock_run_instance(instance_path: String) -> Result<()> {
+pub async fn bedrock_run_instance(instance_path: String, window: tauri::Window) -> Result<()> {
     // Get the instance
     let instance = load_bedrock_instance(&instance_path).await?;
-    
+
+    // Re-hydrate the active account's tokens if they have expired; its
+    // gamertag and picture are the ones cached at sign-in. Bedrock UWP has no public, documented API for injecting a Microsoft/
+    // Xbox session into a launch from outside the app, so this does not sign
+    // the instance in — actual sign-in still depends on whichever Xbox
+    // identity Windows already has signed into Microsoft.MinecraftUWP. We
+    // surface the refreshed account to the frontend so it can at least show
+    // who the instance is configured to run as.
+    let refreshed_account = match instance.account {
+        Some(account_id) => Some(bedrock_auth_refresh(account_id).await?),
+        None => None,
+    };
+    if let Some(account) = &refreshed_account {
+        let _ = window.emit(
+            "bedrock-launch-account",
+            serde_json::json!({
+                "gamertag": account.gamertag,
+                "skinUrl": account.skin_url,
+            }),
+        );
+    }
+
+    // Only one instance can own the shared Microsoft.MinecraftUWP package's
+    // LocalState at a time, so serialize launches on a process-wide lock.
+    let guard = instance_run_lock().lock().await;
+    swap_profile_in(&instance_path).await?;
+
     // Launch Minecraft Bedrock Edition using Windows shell
     // The .appx should be installed as "Microsoft.MinecraftUWP_8wekyb3d8bbwe!App"
     let output = Command::new("cmd")
         .args(&["/C", "start", "minecraft:"])
         .output();
 
-    match output {
+    let launch_result = match output {
         Ok(output) => {
             if !output.status.success() {
                 // Fallback: try launching via PowerShell
@@ -225,20 +721,218 @@ pub async fn bedrock_run_instance(instance_path: String) -> Result<()> {
                         "Get-AppxPackage -Name 'Microsoft.MinecraftUWP' | Invoke-Item"
                     ])
                     .output();
-                
-                if let Err(e) = ps_output {
-                    return Err(format!("Failed to launch Bedrock Edition: {}", e).into());
-                }
+
+                ps_output.map(|_| ()).map_err(|e| format!("Failed to launch Bedrock Edition: {}", e))
+            } else {
+                Ok(())
             }
         }
-        Err(e) => {
-            return Err(format!("Failed to launch Bedrock Edition: {}", e).into());
+        Err(e) => Err(format!("Failed to launch Bedrock Edition: {}", e)),
+    };
+
+    if let Err(e) = launch_result {
+        swap_profile_out(&instance_path).await?;
+        return Err(e.into());
+    }
+
+    // "start minecraft:" returns as soon as the shell hands off to the UWP
+    // host, so restore the shared profile in the background once the game
+    // process exits rather than blocking this command on it.
+    tokio::spawn(async move {
+        wait_for_minecraft_exit().await;
+        if let Err(e) = swap_profile_out(&instance_path).await {
+            eprintln!("Failed to restore shared Bedrock profile: {}", e);
+        }
+        drop(guard);
+    });
+
+    Ok(())
+}
+
+/// Process-wide lock ensuring only one instance swaps the shared
+/// Microsoft.MinecraftUWP `LocalState` folder at a time
+fn instance_run_lock() -> &'static tokio::sync::Mutex<()> {
+    static LOCK: std::sync::OnceLock<tokio::sync::Mutex<()>> = std::sync::OnceLock::new();
+    LOCK.get_or_init(|| tokio::sync::Mutex::new(()))
+}
+
+/// The shared UWP package's `LocalState` directory (worlds, options,
+/// resource packs) that every Bedrock instance otherwise collides on
+fn shared_local_state_dir() -> Result<PathBuf> {
+    let packages_dir = dirs::data_local_dir()
+        .ok_or("Failed to get local app data directory")?
+        .join("Packages");
+
+    Ok(packages_dir
+        .join("Microsoft.MinecraftUWP_8wekyb3d8bbwe")
+        .join("LocalState"))
+}
+
+/// Where the shared `LocalState` is parked while a different instance is
+/// the one actually occupying it
+fn shared_local_state_backup_dir() -> Result<PathBuf> {
+    Ok(shared_local_state_dir()?
+        .parent()
+        .ok_or("LocalState directory has no parent")?
+        .join("LocalState.modrinth-shared-backup"))
+}
+
+/// The instance's own copy of worlds/settings, persisted across launches
+async fn instance_data_dir(instance_path: &str) -> Result<PathBuf> {
+    Ok(instance_addons_root(instance_path).await?.join("data"))
+}
+
+/// Move the instance's saved data into the shared package's `LocalState`
+/// slot, parking whatever was already there so it can be restored later.
+/// If the second move fails, the first is rolled back so `LocalState` is
+/// never left missing.
+async fn swap_profile_in(instance_path: &str) -> Result<()> {
+    let local_state = shared_local_state_dir()?;
+    let backup = shared_local_state_backup_dir()?;
+    let data_dir = instance_data_dir(instance_path).await?;
+
+    fs::create_dir_all(&data_dir).await
+        .map_err(|e| format!("Failed to create instance data directory: {}", e))?;
+
+    let parked = local_state.exists();
+    if parked {
+        fs::rename(&local_state, &backup).await
+            .map_err(|e| format!("Failed to park shared Bedrock profile: {}", e))?;
+    }
+
+    if let Err(e) = fs::rename(&data_dir, &local_state).await {
+        if parked {
+            let _ = fs::rename(&backup, &local_state).await;
+        }
+        return Err(format!("Failed to swap in instance Bedrock profile: {}", e).into());
+    }
+
+    Ok(())
+}
+
+/// Restore the instance's data folder from `LocalState` and put back
+/// whichever profile was parked before this instance launched. If restoring
+/// the parked profile fails, the first move is rolled back so `LocalState`
+/// is never left missing.
+async fn swap_profile_out(instance_path: &str) -> Result<()> {
+    let local_state = shared_local_state_dir()?;
+    let backup = shared_local_state_backup_dir()?;
+    let data_dir = instance_data_dir(instance_path).await?;
+
+    let saved_to_data = local_state.exists();
+    if saved_to_data {
+        fs::rename(&local_state, &data_dir).await
+            .map_err(|e| format!("Failed to save instance Bedrock profile: {}", e))?;
+    }
+
+    if backup.exists() {
+        if let Err(e) = fs::rename(&backup, &local_state).await {
+            if saved_to_data {
+                let _ = fs::rename(&data_dir, &local_state).await;
+            }
+            return Err(format!("Failed to restore shared Bedrock profile: {}", e).into());
         }
     }
 
     Ok(())
 }
 
+/// Poll until the Bedrock UWP process is no longer running
+async fn wait_for_minecraft_exit() {
+    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+    loop {
+        let running = Command::new("tasklist")
+            .args(&["/FI", "IMAGENAME eq Minecraft.Windows.exe"])
+            .output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).contains("Minecraft.Windows.exe"))
+            .unwrap_or(false);
+
+        if !running {
+            break;
+        }
+
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+    }
+}
+
+/// Zip an instance's data folder for backup or sharing. Takes the same
+/// process-wide lock as `bedrock_run_instance` so this can't run while the
+/// instance's data folder is swapped into the shared `LocalState` slot.
+#[tauri::command]
+pub async fn bedrock_export_instance(instance_path: String, dest_path: String) -> Result<()> {
+    let _guard = instance_run_lock().lock().await;
+    let data_dir = instance_data_dir(&instance_path).await?;
+    zip_directory(&data_dir, Path::new(&dest_path)).await
+}
+
+/// Restore an instance's data folder from a zip produced by
+/// [`bedrock_export_instance`]. Takes the same process-wide lock as
+/// `bedrock_run_instance` so this can't run while the instance is active.
+#[tauri::command]
+pub async fn bedrock_import_instance(instance_path: String, src_path: String) -> Result<()> {
+    let _guard = instance_run_lock().lock().await;
+    let data_dir = instance_data_dir(&instance_path).await?;
+    fs::create_dir_all(&data_dir).await
+        .map_err(|e| format!("Failed to create instance data directory: {}", e))?;
+
+    unzip_addon(Path::new(&src_path), &data_dir).await
+}
+
+/// Recursively zip `src` into `dest_zip`, used for instance export
+async fn zip_directory(src: &Path, dest_zip: &Path) -> Result<()> {
+    let src = src.to_path_buf();
+    let dest_zip = dest_zip.to_path_buf();
+
+    tokio::task::spawn_blocking(move || -> Result<()> {
+        let file = std::fs::File::create(&dest_zip)
+            .map_err(|e| format!("Failed to create export archive: {}", e))?;
+        let mut writer = zip::ZipWriter::new(file);
+        let options = zip::write::FileOptions::default();
+
+        fn add_dir(
+            writer: &mut zip::ZipWriter<std::fs::File>,
+            base: &Path,
+            dir: &Path,
+            options: zip::write::FileOptions,
+        ) -> Result<()> {
+            for entry in std::fs::read_dir(dir)
+                .map_err(|e| format!("Failed to read directory {:?}: {}", dir, e))?
+            {
+                let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+                let path = entry.path();
+                let rel = path
+                    .strip_prefix(base)
+                    .map_err(|e| format!("Failed to compute relative path: {}", e))?;
+
+                if path.is_dir() {
+                    writer
+                        .add_directory(rel.to_string_lossy(), options)
+                        .map_err(|e| format!("Failed to add directory to archive: {}", e))?;
+                    add_dir(writer, base, &path, options)?;
+                } else {
+                    writer
+                        .start_file(rel.to_string_lossy(), options)
+                        .map_err(|e| format!("Failed to add file to archive: {}", e))?;
+                    let mut f = std::fs::File::open(&path)
+                        .map_err(|e| format!("Failed to open file for export: {}", e))?;
+                    std::io::copy(&mut f, writer)
+                        .map_err(|e| format!("Failed to write file to archive: {}", e))?;
+                }
+            }
+            Ok(())
+        }
+
+        add_dir(&mut writer, &src, &src, options)?;
+        writer
+            .finish()
+            .map_err(|e| format!("Failed to finalize export archive: {}", e))?;
+
+        Ok(())
+    })
+    .await
+    .map_err(|e| format!("Export task failed: {}", e))?
+}
+
 /// Helper function to get the scripts directory path
 async fn get_scripts_dir() -> Result<PathBuf> {
     // Try bundled scripts first (in app directory)
@@ -248,7 +942,7 @@ async fn get_scripts_dir() -> Result<PathBuf> {
     }
     
     // In development, try relative path to original scripts
-    let dev_scripts = PathBuf::from("../../../ß¬α¿»Γδ/scripts");
+    let dev_scripts = PathBuf::from("../../../scripts");
     if dev_scripts.exists() {
         return Ok(dev_scripts);
     }
@@ -290,10 +984,12 @@ async fn save_bedrock_instance(instance: &BedrockInstance) -> Result<()> {
 
 /// Load Bedrock instance configuration
 async fn load_bedrock_instance(instance_path: &str) -> Result<BedrockInstance> {
+    validate_instance_path(instance_path)?;
+
     let app_dir = dirs::data_dir()
         .ok_or("Failed to get app data directory")?
         .join("com.modrinth.app");
-    
+
     let instance_file = app_dir.join("bedrock_instances").join(format!("{}.json", instance_path));
     let content = fs::read_to_string(&instance_file).await
         .map_err(|e| format!("Failed to load instance: {}", e))?;
@@ -342,19 +1038,624 @@ pub async fn bedrock_list_instances() -> Result<Vec<BedrockInstance>> {
     Ok(instances)
 }
 
-/// Remove a Bedrock instance
+/// Remove a Bedrock instance, along with any addons installed into it
 #[tauri::command]
 pub async fn bedrock_remove_instance(instance_path: String) -> Result<()> {
+    validate_instance_path(&instance_path)?;
+
     let app_dir = dirs::data_dir()
         .ok_or("Failed to get app data directory")?
         .join("com.modrinth.app");
-    
+
     let instance_file = app_dir.join("bedrock_instances").join(format!("{}.json", instance_path));
-    
+
     if instance_file.exists() {
         fs::remove_file(&instance_file).await
             .map_err(|e| format!("Failed to remove instance file: {}", e))?;
     }
-    
+
+    let addons_dir = instance_addons_root(&instance_path).await?;
+    if addons_dir.exists() {
+        fs::remove_dir_all(&addons_dir).await
+            .map_err(|e| format!("Failed to remove instance addons: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Reject an `instance_path` that isn't a single plain path component, since
+/// it's joined directly onto on-disk paths (including the shared
+/// `LocalState` profile swap) and a `..` or absolute path would let it
+/// escape the app's data directory
+fn validate_instance_path(instance_path: &str) -> Result<()> {
+    let path = Path::new(instance_path);
+    let is_single_normal_component = matches!(
+        (path.components().next(), path.components().count()),
+        (Some(std::path::Component::Normal(_)), 1)
+    );
+
+    if instance_path.is_empty() || !is_single_normal_component {
+        return Err(format!("Invalid instance path: {}", instance_path).into());
+    }
+
+    Ok(())
+}
+
+/// Root directory an instance's behavior/resource packs are unpacked into
+async fn instance_addons_root(instance_path: &str) -> Result<PathBuf> {
+    validate_instance_path(instance_path)?;
+
+    let app_dir = dirs::data_dir()
+        .ok_or("Failed to get app data directory")?
+        .join("com.modrinth.app");
+
+    Ok(app_dir.join("bedrock_instances").join(instance_path))
+}
+
+/// Resolve and install a `.mcpack`/`.mcaddon` into an instance's
+/// `behavior_packs`/`resource_packs` folders, registering each pack found
+/// inside in instance JSON. A `.mcaddon` can bundle several packs (e.g. a
+/// behavior pack and its matching resource pack), each with its own
+/// `manifest.json`, so the archive is routed per-pack rather than as a
+/// whole; `kind` is only a fallback for archives with no manifest at all.
+#[tauri::command]
+pub async fn bedrock_add_addon(
+    instance_path: String,
+    name: String,
+    kind: Option<AddonKind>,
+    source: AddonSourceConfig,
+    version: String,
+    window: tauri::Window,
+) -> Result<Vec<BedrockAddon>> {
+    let addon_group_id = uuid::Uuid::new_v4().to_string();
+    let downloads_dir = instance_addons_root(&instance_path).await?.join("downloads");
+    let archive_path = downloads_dir.join(format!("{}.archive", addon_group_id));
+
+    let resolver = addon_source_for(&source);
+    let file = resolver.resolve(&addon_group_id, archive_path.clone()).await?;
+
+    let downloader = Downloader::new();
+    downloader
+        .download(&file, |event| {
+            let _ = window.emit("bedrock-addon-progress", &event);
+        })
+        .await?;
+
+    if let Some(hash) = &file.hash {
+        verify_file_hash(&archive_path, hash).await?;
+    }
+
+    let staging_dir = instance_addons_root(&instance_path)
+        .await?
+        .join("staging")
+        .join(&addon_group_id);
+    unzip_addon(&archive_path, &staging_dir).await?;
+    let _ = fs::remove_file(&archive_path).await;
+
+    let pack_roots = discover_addon_pack_roots(&staging_dir).await?;
+    let pack_roots = if pack_roots.is_empty() {
+        let kind = kind.ok_or("Addon archive has no manifest.json and no pack kind was given")?;
+        vec![(staging_dir.clone(), kind)]
+    } else {
+        pack_roots
+    };
+
+    let mut instance = load_bedrock_instance(&instance_path).await?;
+    let mut addons = Vec::new();
+
+    for (pack_root, pack_kind) in pack_roots {
+        let addon_id = uuid::Uuid::new_v4().to_string();
+        let pack_dir = instance_addons_root(&instance_path)
+            .await?
+            .join(pack_kind.folder_name())
+            .join(&addon_id);
+        if let Some(parent) = pack_dir.parent() {
+            fs::create_dir_all(parent).await
+                .map_err(|e| format!("Failed to create pack directory: {}", e))?;
+        }
+        fs::rename(&pack_root, &pack_dir).await
+            .map_err(|e| format!("Failed to install addon pack: {}", e))?;
+
+        let addon = BedrockAddon {
+            id: addon_id,
+            name: name.clone(),
+            kind: pack_kind,
+            source: source.clone(),
+            version: version.clone(),
+            file_path: pack_dir.to_string_lossy().to_string(),
+        };
+        instance.addons.push(addon.clone());
+        addons.push(addon);
+    }
+
+    let _ = fs::remove_dir_all(&staging_dir).await;
+    save_bedrock_instance(&instance).await?;
+
+    Ok(addons)
+}
+
+/// Walk an extracted addon archive for `manifest.json` files and classify
+/// each pack directory they belong to as behavior or resource, so a
+/// `.mcaddon` bundling both gets routed to the right folders. A directory
+/// is treated as a pack root (and not recursed into further) as soon as it
+/// has its own `manifest.json`, matching how `.mcpack`/`.mcaddon` nest packs.
+async fn discover_addon_pack_roots(dir: &Path) -> Result<Vec<(PathBuf, AddonKind)>> {
+    let dir = dir.to_path_buf();
+    tokio::task::spawn_blocking(move || -> Result<Vec<(PathBuf, AddonKind)>> {
+        fn classify(manifest_path: &Path) -> Result<AddonKind> {
+            let content = std::fs::read_to_string(manifest_path)
+                .map_err(|e| format!("Failed to read manifest.json: {}", e))?;
+            let manifest: serde_json::Value = serde_json::from_str(&content)
+                .map_err(|e| format!("Failed to parse manifest.json: {}", e))?;
+            let module_type = manifest["modules"][0]["type"].as_str().unwrap_or("data");
+            Ok(match module_type {
+                "resources" | "skin_pack" => AddonKind::Resource,
+                _ => AddonKind::Behavior,
+            })
+        }
+
+        fn walk(dir: &Path, found: &mut Vec<(PathBuf, AddonKind)>) -> Result<()> {
+            for entry in std::fs::read_dir(dir)
+                .map_err(|e| format!("Failed to read directory {:?}: {}", dir, e))?
+            {
+                let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+                let path = entry.path();
+                if !path.is_dir() {
+                    continue;
+                }
+
+                let manifest_path = path.join("manifest.json");
+                if manifest_path.exists() {
+                    found.push((path, classify(&manifest_path)?));
+                } else {
+                    walk(&path, found)?;
+                }
+            }
+            Ok(())
+        }
+
+        let mut found = Vec::new();
+        let root_manifest = dir.join("manifest.json");
+        if root_manifest.exists() {
+            found.push((dir.clone(), classify(&root_manifest)?));
+        } else {
+            walk(&dir, &mut found)?;
+        }
+
+        Ok(found)
+    })
+    .await
+    .map_err(|e| format!("Manifest discovery task failed: {}", e))?
+}
+
+/// Unzip a `.mcpack`/`.mcaddon` archive into `dest`
+async fn unzip_addon(archive_path: &Path, dest: &Path) -> Result<()> {
+    let archive_path = archive_path.to_path_buf();
+    let dest = dest.to_path_buf();
+
+    tokio::task::spawn_blocking(move || -> Result<()> {
+        let file = std::fs::File::open(&archive_path)
+            .map_err(|e| format!("Failed to open addon archive: {}", e))?;
+        let mut archive = zip::ZipArchive::new(file)
+            .map_err(|e| format!("Failed to read addon archive: {}", e))?;
+
+        for i in 0..archive.len() {
+            let mut entry = archive
+                .by_index(i)
+                .map_err(|e| format!("Failed to read addon archive entry: {}", e))?;
+            let out_path = match entry.enclosed_name() {
+                Some(name) => dest.join(name),
+                None => continue,
+            };
+
+            if entry.is_dir() {
+                std::fs::create_dir_all(&out_path)
+                    .map_err(|e| format!("Failed to create addon directory: {}", e))?;
+            } else {
+                if let Some(parent) = out_path.parent() {
+                    std::fs::create_dir_all(parent)
+                        .map_err(|e| format!("Failed to create addon directory: {}", e))?;
+                }
+                let mut out_file = std::fs::File::create(&out_path)
+                    .map_err(|e| format!("Failed to write addon file: {}", e))?;
+                std::io::copy(&mut entry, &mut out_file)
+                    .map_err(|e| format!("Failed to extract addon file: {}", e))?;
+            }
+        }
+
+        Ok(())
+    })
+    .await
+    .map_err(|e| format!("Addon extraction task failed: {}", e))?
+}
+
+/// List the addons currently installed into a Bedrock instance
+#[tauri::command]
+pub async fn bedrock_list_addons(instance_path: String) -> Result<Vec<BedrockAddon>> {
+    let instance = load_bedrock_instance(&instance_path).await?;
+    Ok(instance.addons)
+}
+
+/// Remove an installed addon's pack folder and its instance registration
+#[tauri::command]
+pub async fn bedrock_remove_addon(instance_path: String, addon_id: String) -> Result<()> {
+    let mut instance = load_bedrock_instance(&instance_path).await?;
+
+    if let Some(addon) = instance.addons.iter().find(|a| a.id == addon_id) {
+        let pack_dir = PathBuf::from(&addon.file_path);
+        if pack_dir.exists() {
+            fs::remove_dir_all(&pack_dir).await
+                .map_err(|e| format!("Failed to remove addon files: {}", e))?;
+        }
+    }
+
+    instance.addons.retain(|a| a.id != addon_id);
+    save_bedrock_instance(&instance).await?;
+
+    Ok(())
+}
+
+/// A cached, refreshable Microsoft/Xbox credential for one player, keyed by
+/// UUID. Its profile fields are persisted to disk and its tokens to the OS
+/// keyring; launching an instance does not sign it in with this account.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BedrockAccount {
+    pub id: uuid::Uuid,
+    pub gamertag: String,
+    pub skin_url: Option<String>,
+    msa_refresh_token: String,
+    xsts_token: String,
+    user_hash: String,
+    expires_at: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeviceCodeResponse {
+    device_code: String,
+    user_code: String,
+    verification_uri: String,
+    interval: u64,
+    expires_in: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct MsaTokenResponse {
+    access_token: String,
+    refresh_token: String,
+    expires_in: u64,
+}
+
+/// Stage 1: request a device code the user enters at `verification_uri`
+async fn msa_request_device_code(client: &reqwest::Client) -> Result<DeviceCodeResponse> {
+    client
+        .post("https://login.microsoftonline.com/consumers/oauth2/v2.0/devicecode")
+        .form(&[
+            ("client_id", MSA_CLIENT_ID),
+            ("scope", "XboxLive.signin offline_access"),
+        ])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to request Microsoft device code: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse device code response: {}", e).into())
+}
+
+/// Stage 2: poll the token endpoint until the user finishes signing in,
+/// backing off by `interval` seconds as instructed by the device code stage
+async fn msa_poll_device_token(
+    client: &reqwest::Client,
+    device_code: &DeviceCodeResponse,
+) -> Result<MsaTokenResponse> {
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(device_code.expires_in);
+
+    loop {
+        tokio::time::sleep(std::time::Duration::from_secs(device_code.interval)).await;
+
+        let response = client
+            .post("https://login.microsoftonline.com/consumers/oauth2/v2.0/token")
+            .form(&[
+                ("client_id", MSA_CLIENT_ID),
+                ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+                ("device_code", device_code.device_code.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to poll Microsoft token endpoint: {}", e))?;
+
+        if response.status().is_success() {
+            return response
+                .json()
+                .await
+                .map_err(|e| format!("Failed to parse Microsoft token response: {}", e).into());
+        }
+
+        if std::time::Instant::now() >= deadline {
+            return Err("Timed out waiting for Microsoft sign-in".into());
+        }
+    }
+}
+
+/// Stage 3/4: exchange an MSA access token for an Xbox Live (XBL) token,
+/// then an XSTS token scoped to Minecraft services
+async fn xbox_authenticate(client: &reqwest::Client, msa_access_token: &str) -> Result<(String, String)> {
+    let xbl: serde_json::Value = client
+        .post("https://user.auth.xboxlive.com/user/authenticate")
+        .json(&serde_json::json!({
+            "Properties": {
+                "AuthMethod": "RPS",
+                "SiteName": "user.auth.xboxlive.com",
+                "RpsTicket": format!("d={}", msa_access_token),
+            },
+            "RelyingParty": "http://auth.xboxlive.com",
+            "TokenType": "JWT",
+        }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to authenticate with Xbox Live: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Xbox Live response: {}", e))?;
+
+    let xbl_token = xbl["Token"].as_str().ok_or("Xbox Live response missing token")?.to_string();
+
+    let xsts: serde_json::Value = client
+        .post("https://xsts.auth.xboxlive.com/xsts/authorize")
+        .json(&serde_json::json!({
+            "Properties": {
+                "SandboxId": "RETAIL",
+                "UserTokens": [xbl_token],
+            },
+            "RelyingParty": "https://multiplayer.minecraft.net/",
+            "TokenType": "JWT",
+        }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to authorize XSTS token: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse XSTS response: {}", e))?;
+
+    let xsts_token = xsts["Token"].as_str().ok_or("XSTS response missing token")?.to_string();
+    let user_hash = xsts["DisplayClaims"]["xui"][0]["uhs"]
+        .as_str()
+        .ok_or("XSTS response missing user hash")?
+        .to_string();
+
+    Ok((xsts_token, user_hash))
+}
+
+/// Fetch the signed-in player's gamertag and Xbox display picture
+/// (`GameDisplayPicRaw`, returned as the skin URL) from the Xbox profile API;
+/// the player's in-game skin is not fetched
+async fn xbox_fetch_profile(client: &reqwest::Client, xsts_token: &str, user_hash: &str) -> Result<(uuid::Uuid, String, Option<String>)> {
+    let profile: serde_json::Value = client
+        .get("https://profile.xboxlive.com/users/me/profile/settings?settings=Gamertag,GameDisplayPicRaw")
+        .header("Authorization", format!("XBL3.0 x={};{}", user_hash, xsts_token))
+        .header("x-xbl-contract-version", "3")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch Xbox profile: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Xbox profile response: {}", e))?;
+
+    let user = &profile["profileUsers"][0];
+    let xuid = user["id"].as_str().ok_or("Xbox profile missing xuid")?;
+    let id = uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_OID, xuid.as_bytes());
+
+    let mut gamertag = xuid.to_string();
+    let mut skin_url = None;
+    if let Some(settings) = user["settings"].as_array() {
+        for setting in settings {
+            match setting["id"].as_str() {
+                Some("Gamertag") => {
+                    if let Some(value) = setting["value"].as_str() {
+                        gamertag = value.to_string();
+                    }
+                }
+                Some("GameDisplayPicRaw") => {
+                    skin_url = setting["value"].as_str().map(|v| v.to_string());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok((id, gamertag, skin_url))
+}
+
+/// Directory cached Microsoft/Xbox account profiles are stored in, keyed by
+/// UUID. Only non-sensitive fields live here — tokens go to the OS keyring.
+async fn accounts_dir() -> Result<PathBuf> {
+    let app_dir = dirs::data_dir()
+        .ok_or("Failed to get app data directory")?
+        .join("com.modrinth.app");
+
+    let dir = app_dir.join("bedrock_accounts");
+    fs::create_dir_all(&dir).await
+        .map_err(|e| format!("Failed to create accounts directory: {}", e))?;
+
+    Ok(dir)
+}
+
+/// The non-sensitive half of a [`BedrockAccount`], persisted to disk in
+/// plain JSON
+#[derive(Debug, Serialize, Deserialize)]
+struct BedrockAccountProfile {
+    id: uuid::Uuid,
+    gamertag: String,
+    skin_url: Option<String>,
+    expires_at: u64,
+}
+
+/// The sensitive half — MSA/XBL/XSTS material — stored only in the OS
+/// keyring, the same approach theseus uses for Minecraft account tokens
+#[derive(Debug, Serialize, Deserialize)]
+struct BedrockAccountSecrets {
+    msa_refresh_token: String,
+    xsts_token: String,
+    user_hash: String,
+}
+
+fn bedrock_keyring_entry(account_id: uuid::Uuid) -> Result<keyring::Entry> {
+    keyring::Entry::new("com.modrinth.app.bedrock", &account_id.to_string())
+        .map_err(|e| format!("Failed to open OS keyring entry: {}", e).into())
+}
+
+async fn save_bedrock_account(account: &BedrockAccount) -> Result<()> {
+    let secrets = BedrockAccountSecrets {
+        msa_refresh_token: account.msa_refresh_token.clone(),
+        xsts_token: account.xsts_token.clone(),
+        user_hash: account.user_hash.clone(),
+    };
+    let secrets_json = serde_json::to_string(&secrets)
+        .map_err(|e| format!("Failed to serialize account credentials: {}", e))?;
+
+    let account_id = account.id;
+    tokio::task::spawn_blocking(move || -> Result<()> {
+        bedrock_keyring_entry(account_id)?
+            .set_password(&secrets_json)
+            .map_err(|e| format!("Failed to store account credentials in OS keyring: {}", e).into())
+    })
+    .await
+    .map_err(|e| format!("Keyring task failed: {}", e))??;
+
+    let profile = BedrockAccountProfile {
+        id: account.id,
+        gamertag: account.gamertag.clone(),
+        skin_url: account.skin_url.clone(),
+        expires_at: account.expires_at,
+    };
+    let path = accounts_dir().await?.join(format!("{}.json", account.id));
+    let json = serde_json::to_string_pretty(&profile)
+        .map_err(|e| format!("Failed to serialize account profile: {}", e))?;
+    fs::write(&path, json).await
+        .map_err(|e| format!("Failed to save account profile: {}", e))?;
+
     Ok(())
+}
+
+async fn load_bedrock_account(id: uuid::Uuid) -> Result<BedrockAccount> {
+    let path = accounts_dir().await?.join(format!("{}.json", id));
+    let content = fs::read_to_string(&path).await
+        .map_err(|e| format!("Failed to load account profile: {}", e))?;
+    let profile: BedrockAccountProfile = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse account profile: {}", e))?;
+
+    let secrets_json = tokio::task::spawn_blocking(move || -> Result<String> {
+        bedrock_keyring_entry(id)?
+            .get_password()
+            .map_err(|e| format!("Failed to read account credentials from OS keyring: {}", e).into())
+    })
+    .await
+    .map_err(|e| format!("Keyring task failed: {}", e))??;
+
+    let secrets: BedrockAccountSecrets = serde_json::from_str(&secrets_json)
+        .map_err(|e| format!("Failed to parse account credentials: {}", e))?;
+
+    Ok(BedrockAccount {
+        id: profile.id,
+        gamertag: profile.gamertag,
+        skin_url: profile.skin_url,
+        msa_refresh_token: secrets.msa_refresh_token,
+        xsts_token: secrets.xsts_token,
+        user_hash: secrets.user_hash,
+        expires_at: profile.expires_at,
+    })
+}
+
+fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Perform the full Microsoft device-code sign-in flow (MSA -> XBL -> XSTS),
+/// caching the refreshable credential keyed by the player's UUID (profile on
+/// disk, tokens in the OS keyring)
+#[tauri::command]
+pub async fn bedrock_auth_login(window: tauri::Window) -> Result<BedrockAccount> {
+    let client = reqwest::Client::new();
+
+    let device_code = msa_request_device_code(&client).await?;
+    let _ = window.emit(
+        "bedrock-auth-status",
+        serde_json::json!({
+            "verificationUri": device_code.verification_uri,
+            "userCode": device_code.user_code,
+        }),
+    );
+
+    let msa_token = msa_poll_device_token(&client, &device_code).await?;
+    let (xsts_token, user_hash) = xbox_authenticate(&client, &msa_token.access_token).await?;
+    let (id, gamertag, skin_url) = xbox_fetch_profile(&client, &xsts_token, &user_hash).await?;
+
+    let account = BedrockAccount {
+        id,
+        gamertag,
+        skin_url,
+        msa_refresh_token: msa_token.refresh_token,
+        xsts_token,
+        user_hash,
+        expires_at: unix_timestamp() + msa_token.expires_in,
+    };
+
+    save_bedrock_account(&account).await?;
+    let _ = window.emit("bedrock-auth-status", "signed in");
+
+    Ok(account)
+}
+
+/// Re-hydrate an expired cached credential using its MSA refresh token
+#[tauri::command]
+pub async fn bedrock_auth_refresh(account_id: uuid::Uuid) -> Result<BedrockAccount> {
+    let cached = load_bedrock_account(account_id).await?;
+
+    if unix_timestamp() < cached.expires_at {
+        return Ok(cached);
+    }
+
+    let client = reqwest::Client::new();
+    let msa_token: MsaTokenResponse = client
+        .post("https://login.microsoftonline.com/consumers/oauth2/v2.0/token")
+        .form(&[
+            ("client_id", MSA_CLIENT_ID),
+            ("grant_type", "refresh_token"),
+            ("refresh_token", cached.msa_refresh_token.as_str()),
+            ("scope", "XboxLive.signin offline_access"),
+        ])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to refresh Microsoft token: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse refreshed token response: {}", e))?;
+
+    let (xsts_token, user_hash) = xbox_authenticate(&client, &msa_token.access_token).await?;
+
+    let account = BedrockAccount {
+        id: cached.id,
+        gamertag: cached.gamertag,
+        skin_url: cached.skin_url,
+        msa_refresh_token: msa_token.refresh_token,
+        xsts_token,
+        user_hash,
+        expires_at: unix_timestamp() + msa_token.expires_in,
+    };
+
+    save_bedrock_account(&account).await?;
+
+    Ok(account)
+}
+
+/// Set the account an instance is configured to run as. The launch does not
+/// sign the game in with it; it is only refreshed and reported to the frontend.
+#[tauri::command]
+pub async fn bedrock_set_instance_account(instance_path: String, account_id: Option<uuid::Uuid>) -> Result<BedrockInstance> {
+    let mut instance = load_bedrock_instance(&instance_path).await?;
+    instance.account = account_id;
+    save_bedrock_instance(&instance).await?;
+    Ok(instance)
 }
\ No newline at end of file